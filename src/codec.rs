@@ -0,0 +1,393 @@
+use crate::types::TypeKind;
+use crate::{Field, Parameter, Type, TypeVar, Variant};
+
+const TAG_INT: u8 = 0;
+const TAG_UINT: u8 = 1;
+const TAG_ENUM: u8 = 2;
+const TAG_STRUCT: u8 = 3;
+const TAG_ARRAY: u8 = 4;
+const TAG_FUNCTION: u8 = 5;
+const TAG_VAR: u8 = 6;
+const TAG_FORALL: u8 = 7;
+
+/// Errors produced while decoding a [`Type`] from its binary encoding.
+#[derive(Debug, thiserror::Error)]
+pub enum DecodeError {
+    /// The input ended before a complete type descriptor was read.
+    #[error("unexpected end of input")]
+    UnexpectedEof,
+    /// The tag byte didn't match any known `TypeKind`.
+    #[error("unknown type kind tag: {0}")]
+    UnknownTag(u8),
+    /// A length-prefixed name was not valid UTF-8.
+    #[error("encoded name is not valid UTF-8")]
+    InvalidUtf8,
+    /// A decoded struct or variant listed the same field name twice.
+    #[error("decoded a struct or variant with a duplicate field name")]
+    DuplicateField,
+    /// A decoded enum listed the same variant name twice.
+    #[error("decoded an enum with a duplicate variant name")]
+    DuplicateVariant,
+}
+
+impl Type {
+    /// Encodes this type into a compact, self-describing binary format.
+    ///
+    /// The format is a one-byte `TypeKind` tag, the type's optional name as
+    /// a length-prefixed UTF-8 string, and then a kind-specific payload,
+    /// recursing into child types the same way.
+    #[must_use]
+    pub fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        encode_type(self, &mut buf);
+        buf
+    }
+
+    /// Decodes a type previously produced by [`Type::encode`].
+    ///
+    /// Guards against truncated input, unknown tags, names that are not
+    /// valid UTF-8, and duplicate field or variant names; other
+    /// malformed-but-complete encodings are not guarded against (an integer
+    /// width above 4096 bits, for instance, reaches `IntBuilder::set_bits`
+    /// and panics there).
+    pub fn decode(bytes: &[u8]) -> Result<Type, DecodeError> {
+        let mut reader = Reader::new(bytes);
+        decode_type(&mut reader)
+    }
+}
+
+fn encode_type(ty: &Type, buf: &mut Vec<u8>) {
+    match ty.kind() {
+        TypeKind::Int(int_type) => {
+            buf.push(TAG_INT);
+            write_option_string(ty.name(), buf);
+            write_varint(u64::from(int_type.bits()), buf);
+        }
+        TypeKind::UInt(int_type) => {
+            buf.push(TAG_UINT);
+            write_option_string(ty.name(), buf);
+            write_varint(u64::from(int_type.bits()), buf);
+        }
+        TypeKind::Array(array) => {
+            buf.push(TAG_ARRAY);
+            write_option_string(ty.name(), buf);
+            write_varint(array.len() as u64, buf);
+            encode_type(array.element_type(), buf);
+        }
+        TypeKind::Struct(structure) => {
+            buf.push(TAG_STRUCT);
+            write_option_string(ty.name(), buf);
+            write_varint(structure.fields().len() as u64, buf);
+            for field in structure.fields() {
+                write_option_string(field.name(), buf);
+                encode_type(field.field_type(), buf);
+            }
+        }
+        TypeKind::Enum(enumeration) => {
+            buf.push(TAG_ENUM);
+            write_option_string(ty.name(), buf);
+            write_varint(enumeration.variants().len() as u64, buf);
+            for variant in enumeration.variants() {
+                write_string(variant.name(), buf);
+                match variant.discriminant() {
+                    None => buf.push(0),
+                    Some(discriminant) => {
+                        buf.push(1);
+                        buf.extend_from_slice(&discriminant.to_le_bytes());
+                    }
+                }
+                write_varint(variant.fields().len() as u64, buf);
+                for field in variant.fields() {
+                    write_option_string(field.name(), buf);
+                    encode_type(field.field_type(), buf);
+                }
+            }
+        }
+        TypeKind::Function(function) => {
+            buf.push(TAG_FUNCTION);
+            write_option_string(ty.name(), buf);
+            write_varint(function.parameters().len() as u64, buf);
+            for param in function.parameters() {
+                encode_type(param.parameter_type(), buf);
+            }
+            encode_type(function.return_type(), buf);
+        }
+        TypeKind::Var(var) => {
+            buf.push(TAG_VAR);
+            write_option_string(ty.name(), buf);
+            write_varint(u64::from(var.id()), buf);
+            write_option_string(var.name(), buf);
+        }
+        TypeKind::Forall(forall) => {
+            buf.push(TAG_FORALL);
+            write_option_string(ty.name(), buf);
+            write_varint(forall.vars().len() as u64, buf);
+            for var in forall.vars() {
+                write_varint(u64::from(var.id()), buf);
+                write_option_string(var.name(), buf);
+            }
+            encode_type(forall.body(), buf);
+        }
+    }
+}
+
+fn decode_type(reader: &mut Reader<'_>) -> Result<Type, DecodeError> {
+    let tag = reader.read_u8()?;
+    let name = reader.read_option_string()?;
+
+    match tag {
+        TAG_INT | TAG_UINT => {
+            let bits = reader.read_varint()? as u16;
+            let mut builder = Type::builder(name.unwrap_or_default());
+            if tag == TAG_INT {
+                builder.int().set_bits(bits).finish();
+            } else {
+                builder.uint().set_bits(bits).finish();
+            }
+            Ok(builder.build())
+        }
+        TAG_ARRAY => {
+            let len = reader.read_varint()? as usize;
+            let element = decode_type(reader)?;
+            let mut builder = Type::builder(name.unwrap_or_default());
+            builder.array().set_element_type(element).len(len).finish();
+            Ok(builder.build())
+        }
+        TAG_STRUCT => {
+            let field_count = reader.read_varint()?;
+            let mut builder = Type::builder(name.unwrap_or_default());
+            {
+                let mut struct_builder = builder.structure();
+                for _ in 0..field_count {
+                    let field_name = reader.read_option_string()?;
+                    let field_type = decode_type(reader)?;
+                    struct_builder = struct_builder
+                        .add_field(Field::new(field_name, field_type))
+                        .map_err(|_| DecodeError::DuplicateField)?;
+                }
+                struct_builder.finish();
+            }
+            Ok(builder.build())
+        }
+        TAG_ENUM => {
+            let variant_count = reader.read_varint()?;
+            let mut builder = Type::builder(name.unwrap_or_default());
+            {
+                let mut enum_builder = builder.enumeration();
+                for _ in 0..variant_count {
+                    let variant_name = reader.read_string()?;
+                    let mut variant_builder = Variant::builder(variant_name);
+                    if reader.read_u8()? != 0 {
+                        let discriminant = reader.read_i64()?;
+                        variant_builder = variant_builder.set_discriminant(discriminant);
+                    }
+                    let field_count = reader.read_varint()?;
+                    for _ in 0..field_count {
+                        let field_name = reader.read_option_string()?;
+                        let field_type = decode_type(reader)?;
+                        variant_builder = variant_builder
+                            .add_field(Field::new(field_name, field_type))
+                            .map_err(|_| DecodeError::DuplicateField)?;
+                    }
+                    enum_builder = enum_builder
+                        .add_variant(variant_builder)
+                        .map_err(|_| DecodeError::DuplicateVariant)?;
+                }
+                enum_builder.finish();
+            }
+            Ok(builder.build())
+        }
+        TAG_FUNCTION => {
+            let param_count = reader.read_varint()?;
+            let mut builder = Type::builder(name.unwrap_or_default());
+            {
+                let mut function_builder = builder.function();
+                for index in 0..param_count {
+                    let param_type = decode_type(reader)?;
+                    let param = Parameter::new(format!("_{index}"), param_type);
+                    function_builder = function_builder.add_parameter(param);
+                }
+                let return_type = decode_type(reader)?;
+                function_builder = function_builder.set_return_type(return_type);
+                function_builder.finish();
+            }
+            Ok(builder.build())
+        }
+        TAG_VAR => {
+            let id = reader.read_varint()? as u32;
+            let var_name = reader.read_option_string()?;
+            Ok(Type::from_var(name, TypeVar::from_raw(id, var_name)))
+        }
+        TAG_FORALL => {
+            let var_count = reader.read_varint()?;
+            let mut vars = Vec::new();
+            for _ in 0..var_count {
+                let id = reader.read_varint()? as u32;
+                let var_name = reader.read_option_string()?;
+                vars.push(TypeVar::from_raw(id, var_name));
+            }
+            let body = decode_type(reader)?;
+            let mut builder = Type::builder(name.unwrap_or_default());
+            builder.forall(vars, body);
+            Ok(builder.build())
+        }
+        other => Err(DecodeError::UnknownTag(other)),
+    }
+}
+
+fn write_varint(mut value: u64, buf: &mut Vec<u8>) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            buf.push(byte);
+            break;
+        }
+        buf.push(byte | 0x80);
+    }
+}
+
+fn write_string(s: &str, buf: &mut Vec<u8>) {
+    write_varint(s.len() as u64, buf);
+    buf.extend_from_slice(s.as_bytes());
+}
+
+fn write_option_string(s: Option<&str>, buf: &mut Vec<u8>) {
+    match s {
+        None => buf.push(0),
+        Some(s) => {
+            buf.push(1);
+            write_string(s, buf);
+        }
+    }
+}
+
+/// A cursor over an encoded byte slice, used only while decoding.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, pos: 0 }
+    }
+
+    fn read_u8(&mut self) -> Result<u8, DecodeError> {
+        let byte = *self
+            .bytes
+            .get(self.pos)
+            .ok_or(DecodeError::UnexpectedEof)?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        let end = self.pos.checked_add(len).ok_or(DecodeError::UnexpectedEof)?;
+        let slice = self
+            .bytes
+            .get(self.pos..end)
+            .ok_or(DecodeError::UnexpectedEof)?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn read_varint(&mut self) -> Result<u64, DecodeError> {
+        let mut result = 0u64;
+        let mut shift = 0;
+        loop {
+            let byte = self.read_u8()?;
+            result |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+            shift += 7;
+        }
+        Ok(result)
+    }
+
+    fn read_i64(&mut self) -> Result<i64, DecodeError> {
+        let bytes: [u8; 8] = self
+            .read_bytes(8)?
+            .try_into()
+            .map_err(|_| DecodeError::UnexpectedEof)?;
+        Ok(i64::from_le_bytes(bytes))
+    }
+
+    fn read_string(&mut self) -> Result<String, DecodeError> {
+        let len = self.read_varint()? as usize;
+        let bytes = self.read_bytes(len)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| DecodeError::InvalidUtf8)
+    }
+
+    fn read_option_string(&mut self) -> Result<Option<String>, DecodeError> {
+        match self.read_u8()? {
+            0 => Ok(None),
+            _ => Ok(Some(self.read_string()?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn int_round_trips() {
+        let mut builder = Type::builder("MyInt".to_string());
+        builder.int().set_bits(32).finish();
+        let ty = builder.build();
+
+        assert_eq!(Type::decode(&ty.encode()).unwrap(), ty);
+    }
+
+    #[test]
+    fn named_type_var_round_trips_its_name() {
+        let var = TypeVar::fresh(Some("T"));
+        let ty = Type::from_var(Some("MyNamedVar".to_string()), var);
+
+        let decoded = Type::decode(&ty.encode()).unwrap();
+        assert_eq!(decoded.name(), Some("MyNamedVar"));
+    }
+
+    #[test]
+    fn decoding_a_var_reserves_its_id_against_later_fresh_vars() {
+        let var = TypeVar::fresh(Some("T"));
+        let ty = Type::from_var(None, var);
+        let encoded = ty.encode();
+
+        let decoded = Type::decode(&encoded).unwrap();
+        let decoded_id = match decoded.kind() {
+            TypeKind::Var(var) => var.id(),
+            other => panic!("expected a Var, got {other:?}"),
+        };
+        let later = TypeVar::fresh(Some("U"));
+        assert_ne!(decoded_id, later.id());
+    }
+
+    #[test]
+    fn truncated_input_is_an_error_not_a_panic() {
+        assert!(matches!(
+            Type::decode(&[]),
+            Err(DecodeError::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn huge_forall_var_count_does_not_panic_on_truncated_input() {
+        // tag Forall, no name, var_count = u64::MAX, then nothing else.
+        let bytes = [7, 0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01];
+        assert!(matches!(
+            Type::decode(&bytes),
+            Err(DecodeError::UnexpectedEof)
+        ));
+    }
+
+    #[test]
+    fn unknown_tag_is_an_error() {
+        assert!(matches!(
+            Type::decode(&[0xff, 0]),
+            Err(DecodeError::UnknownTag(0xff))
+        ));
+    }
+}
@@ -6,8 +6,74 @@ pub struct Dynamic {
     _value: Box<[u8]>,
 }
 
+/// Errors produced while constructing a [`Dynamic`].
+#[derive(Debug, thiserror::Error)]
+pub enum DynamicError {
+    /// The value buffer doesn't match the size the type's computed layout
+    /// requires.
+    #[error("value buffer of {actual} bytes does not match the {expected}-byte layout of the type")]
+    SizeMismatch { expected: usize, actual: usize },
+    /// The type is non-concrete (a type variable or a universally-quantified
+    /// scheme), so it has no layout to check the value buffer against.
+    #[error("cannot hold a value of a non-concrete (generic) type; instantiate it first")]
+    NonConcreteType,
+}
+
 impl Dynamic {
+    /// Creates a `Dynamic` value, checking that `value` is exactly as large
+    /// as `ty`'s computed layout requires.
+    pub fn new(ty: Type, value: Box<[u8]>) -> Result<Self, DynamicError> {
+        let expected = ty.try_layout().ok_or(DynamicError::NonConcreteType)?.size();
+        if value.len() == expected {
+            Ok(Self { ty, _value: value })
+        } else {
+            Err(DynamicError::SizeMismatch {
+                expected,
+                actual: value.len(),
+            })
+        }
+    }
+
     pub fn value_type(&self) -> &Type {
         &self.ty
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int(bits: u16) -> Type {
+        let mut builder = Type::builder(String::new());
+        builder.int().set_bits(bits).finish();
+        builder.build()
+    }
+
+    #[test]
+    fn accepts_a_correctly_sized_buffer() {
+        assert!(Dynamic::new(int(32), Box::new([0; 4])).is_ok());
+    }
+
+    #[test]
+    fn rejects_a_mismatched_buffer_size() {
+        assert!(matches!(
+            Dynamic::new(int(32), Box::new([0; 3])),
+            Err(DynamicError::SizeMismatch {
+                expected: 4,
+                actual: 3
+            })
+        ));
+    }
+
+    #[test]
+    fn rejects_a_non_concrete_type_instead_of_panicking() {
+        let mut builder = Type::builder(String::new());
+        builder.type_var("T");
+        let var = builder.build();
+
+        assert!(matches!(
+            Dynamic::new(var, Box::new([])),
+            Err(DynamicError::NonConcreteType)
+        ));
+    }
+}
@@ -0,0 +1,426 @@
+use std::collections::HashMap;
+
+use crate::types::{Forall, TypeKind, TypeVar};
+use crate::{Field, Parameter, Type, Variant};
+
+/// Errors produced while unifying two [`Type`]s.
+#[derive(Debug, thiserror::Error)]
+pub enum UnifyError {
+    /// The two types have incompatible top-level constructors, or are
+    /// structs, enums or variants whose field or variant counts differ.
+    #[error("cannot unify incompatible types")]
+    Mismatch,
+    /// Two integer types disagree on bit width.
+    #[error("integer types of different widths cannot be unified")]
+    BitWidthMismatch,
+    /// Two array types disagree on length.
+    #[error("array types of different lengths cannot be unified")]
+    LengthMismatch,
+    /// Two function types disagree on arity.
+    #[error("function types of different arity cannot be unified")]
+    ArityMismatch,
+    /// Binding a variable would create a cyclic (infinitely recursive) type.
+    #[error("cannot construct an infinite type")]
+    InfiniteType,
+    /// One side is a universally-quantified type scheme, which unification
+    /// cannot structurally compare; instantiate it first.
+    #[error("cannot unify a universally-quantified type scheme; instantiate it first")]
+    UnquantifiedForall,
+}
+
+/// The bindings produced by a successful unification.
+#[derive(Debug, Default, Clone)]
+pub struct Substitution {
+    bindings: HashMap<u32, Type>,
+}
+
+impl Substitution {
+    /// Creates an empty substitution.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Looks up the type a variable was bound to, if any.
+    pub fn get(&self, var: u32) -> Option<&Type> {
+        self.bindings.get(&var)
+    }
+
+    fn merge(mut self, other: Self) -> Self {
+        self.bindings.extend(other.bindings);
+        self
+    }
+}
+
+/// Unifies two types structurally, returning the [`Substitution`] required
+/// to make them equal.
+///
+/// Identical constructors recurse into their children pairwise; mismatched
+/// constructors are reported as an error. A [`TypeKind::Forall`] on either
+/// side is rejected rather than compared structurally — instantiate it with
+/// [`instantiate`] first.
+pub fn unify(lhs: &Type, rhs: &Type) -> Result<Substitution, UnifyError> {
+    match (lhs.kind(), rhs.kind()) {
+        (TypeKind::Var(a), TypeKind::Var(b)) if a == b => Ok(Substitution::new()),
+        (TypeKind::Forall(_), _) | (_, TypeKind::Forall(_)) => {
+            Err(UnifyError::UnquantifiedForall)
+        }
+        (TypeKind::Var(var), _) => bind(var, rhs),
+        (_, TypeKind::Var(var)) => bind(var, lhs),
+        (TypeKind::Int(a), TypeKind::Int(b)) | (TypeKind::UInt(a), TypeKind::UInt(b)) => {
+            if a.bits() == b.bits() {
+                Ok(Substitution::new())
+            } else {
+                Err(UnifyError::BitWidthMismatch)
+            }
+        }
+        (TypeKind::Array(a), TypeKind::Array(b)) => {
+            if a.len() != b.len() {
+                return Err(UnifyError::LengthMismatch);
+            }
+            unify(a.element_type(), b.element_type())
+        }
+        (TypeKind::Struct(a), TypeKind::Struct(b)) => {
+            if a.fields().len() != b.fields().len() {
+                return Err(UnifyError::Mismatch);
+            }
+            a.fields()
+                .iter()
+                .zip(b.fields())
+                .try_fold(Substitution::new(), |subst, (fa, fb)| {
+                    Ok(subst.merge(unify(fa.field_type(), fb.field_type())?))
+                })
+        }
+        (TypeKind::Enum(a), TypeKind::Enum(b)) => {
+            if a.variants().len() != b.variants().len() {
+                return Err(UnifyError::Mismatch);
+            }
+            a.variants()
+                .iter()
+                .zip(b.variants())
+                .try_fold(Substitution::new(), |subst, (va, vb)| {
+                    if va.fields().len() != vb.fields().len() {
+                        return Err(UnifyError::Mismatch);
+                    }
+                    va.fields().iter().zip(vb.fields()).try_fold(
+                        subst,
+                        |subst, (fa, fb)| {
+                            Ok(subst.merge(unify(fa.field_type(), fb.field_type())?))
+                        },
+                    )
+                })
+        }
+        (TypeKind::Function(a), TypeKind::Function(b)) => {
+            if a.parameters().len() != b.parameters().len() {
+                return Err(UnifyError::ArityMismatch);
+            }
+            let subst = a.parameters().iter().zip(b.parameters()).try_fold(
+                Substitution::new(),
+                |subst, (pa, pb)| {
+                    Ok(subst.merge(unify(pa.parameter_type(), pb.parameter_type())?))
+                },
+            )?;
+            Ok(subst.merge(unify(a.return_type(), b.return_type())?))
+        }
+        _ => Err(UnifyError::Mismatch),
+    }
+}
+
+/// Binds `var` to `ty`, rejecting the binding if it would construct an
+/// infinite type.
+fn bind(var: &TypeVar, ty: &Type) -> Result<Substitution, UnifyError> {
+    if occurs(var, ty) {
+        return Err(UnifyError::InfiniteType);
+    }
+    let mut subst = Substitution::new();
+    subst.bindings.insert(var.id(), ty.clone());
+    Ok(subst)
+}
+
+/// Returns whether `var` occurs free anywhere within `ty`.
+fn occurs(var: &TypeVar, ty: &Type) -> bool {
+    match ty.kind() {
+        TypeKind::Var(v) => v == var,
+        TypeKind::Int(_) | TypeKind::UInt(_) => false,
+        TypeKind::Array(array) => occurs(var, array.element_type()),
+        TypeKind::Struct(structure) => {
+            structure.fields().iter().any(|f| occurs(var, f.field_type()))
+        }
+        TypeKind::Enum(enumeration) => enumeration
+            .variants()
+            .iter()
+            .flat_map(|v| v.fields())
+            .any(|f| occurs(var, f.field_type())),
+        TypeKind::Function(function) => {
+            function
+                .parameters()
+                .iter()
+                .any(|p| occurs(var, p.parameter_type()))
+                || occurs(var, function.return_type())
+        }
+        TypeKind::Forall(forall) => !forall.vars().contains(var) && occurs(var, forall.body()),
+    }
+}
+
+/// Scans `ty` for free type variables not bound in the surrounding
+/// environment and, if any remain, wraps `ty` in a [`Forall`] quantifying
+/// over them.
+///
+/// `env` lists the variables still bound by an enclosing scope (e.g. an
+/// outer `let`); they are excluded from generalization so `ty` isn't
+/// quantified over a variable that isn't actually free at this point.
+pub fn generalize(ty: Type, env: &[TypeVar]) -> Type {
+    let mut vars = Vec::new();
+    free_vars(&ty, &mut vars);
+    vars.retain(|var| !env.contains(var));
+    if vars.is_empty() {
+        ty
+    } else {
+        let mut builder = Type::builder(String::new());
+        builder.forall(vars, ty);
+        builder.build()
+    }
+}
+
+fn free_vars(ty: &Type, out: &mut Vec<TypeVar>) {
+    match ty.kind() {
+        TypeKind::Var(var) => {
+            if !out.contains(var) {
+                out.push(var.clone());
+            }
+        }
+        TypeKind::Int(_) | TypeKind::UInt(_) => {}
+        TypeKind::Array(array) => free_vars(array.element_type(), out),
+        TypeKind::Struct(structure) => {
+            for field in structure.fields() {
+                free_vars(field.field_type(), out);
+            }
+        }
+        TypeKind::Enum(enumeration) => {
+            for field in enumeration.variants().iter().flat_map(|v| v.fields()) {
+                free_vars(field.field_type(), out);
+            }
+        }
+        TypeKind::Function(function) => {
+            for param in function.parameters() {
+                free_vars(param.parameter_type(), out);
+            }
+            free_vars(function.return_type(), out);
+        }
+        TypeKind::Forall(forall) => {
+            let mut inner = Vec::new();
+            free_vars(forall.body(), &mut inner);
+            out.extend(inner.into_iter().filter(|v| !forall.vars().contains(v)));
+        }
+    }
+}
+
+/// Replaces each variable bound by `forall` with a fresh one, so every
+/// instantiation site unifies against independent variables.
+pub fn instantiate(forall: &Forall) -> Type {
+    let fresh: HashMap<u32, TypeVar> = forall
+        .vars()
+        .iter()
+        .map(|var| (var.id(), TypeVar::fresh(var.name().map(str::to_owned))))
+        .collect();
+    substitute(forall.body(), &fresh)
+}
+
+fn substitute(ty: &Type, fresh: &HashMap<u32, TypeVar>) -> Type {
+    match ty.kind() {
+        TypeKind::Var(var) => match fresh.get(&var.id()) {
+            Some(replacement) => Type::from_var(ty.name().map(str::to_owned), replacement.clone()),
+            None => ty.clone(),
+        },
+        TypeKind::Int(_) | TypeKind::UInt(_) => ty.clone(),
+        TypeKind::Array(array) => {
+            let element = substitute(array.element_type(), fresh);
+            let mut builder = Type::builder(String::new());
+            builder
+                .array()
+                .set_element_type(element)
+                .len(array.len())
+                .finish();
+            builder.build()
+        }
+        TypeKind::Struct(structure) => {
+            let mut builder = Type::builder(ty.name().unwrap_or_default().to_string());
+            {
+                let mut sb = builder.structure();
+                for field in structure.fields() {
+                    let new_ty = substitute(field.field_type(), fresh);
+                    sb = sb
+                        .add_field(Field::new(field.name(), new_ty))
+                        .expect("rebuilt struct cannot introduce duplicate fields");
+                }
+                sb.finish();
+            }
+            builder.build()
+        }
+        TypeKind::Enum(enumeration) => {
+            let mut builder = Type::builder(ty.name().unwrap_or_default().to_string());
+            {
+                let mut eb = builder.enumeration();
+                for variant in enumeration.variants() {
+                    let mut vb = Variant::builder(variant.name());
+                    if let Some(discriminant) = variant.discriminant() {
+                        vb = vb.set_discriminant(discriminant);
+                    }
+                    for field in variant.fields() {
+                        let new_ty = substitute(field.field_type(), fresh);
+                        vb = vb
+                            .add_field(Field::new(field.name(), new_ty))
+                            .expect("rebuilt variant cannot introduce duplicate fields");
+                    }
+                    eb = eb
+                        .add_variant(vb)
+                        .expect("rebuilt enum cannot introduce duplicate variants");
+                }
+                eb.finish();
+            }
+            builder.build()
+        }
+        TypeKind::Function(function) => {
+            let mut builder = Type::builder(ty.name().unwrap_or_default().to_string());
+            {
+                let mut fb = builder.function();
+                for param in function.parameters() {
+                    let new_ty = substitute(param.parameter_type(), fresh);
+                    fb = fb.add_parameter(Parameter::new(param.name(), new_ty));
+                }
+                fb = fb.set_return_type(substitute(function.return_type(), fresh));
+                if let Some(body) = function.body() {
+                    fb = fb.set_body(body);
+                }
+                fb.finish();
+            }
+            builder.build()
+        }
+        TypeKind::Forall(forall) => {
+            let mut inner_fresh = fresh.clone();
+            for var in forall.vars() {
+                inner_fresh.remove(&var.id());
+            }
+            let body = substitute(forall.body(), &inner_fresh);
+            let mut builder = Type::builder(String::new());
+            builder.forall(forall.vars().to_vec(), body);
+            builder.build()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int(bits: u16) -> Type {
+        let mut builder = Type::builder(String::new());
+        builder.int().set_bits(bits).finish();
+        builder.build()
+    }
+
+    #[test]
+    fn unify_identical_ints_succeeds() {
+        assert!(unify(&int(32), &int(32)).is_ok());
+    }
+
+    #[test]
+    fn unify_mismatched_bit_widths_fails() {
+        assert!(matches!(
+            unify(&int(32), &int(64)),
+            Err(UnifyError::BitWidthMismatch)
+        ));
+    }
+
+    #[test]
+    fn unify_var_with_concrete_type_binds_it() {
+        let var = TypeVar::fresh(Some("T"));
+        let var_ty = Type::from_var(None, var.clone());
+        let subst = unify(&var_ty, &int(32)).unwrap();
+        assert_eq!(subst.get(var.id()), Some(&int(32)));
+    }
+
+    #[test]
+    fn unify_var_with_itself_is_a_no_op() {
+        let var = TypeVar::fresh(Some("T"));
+        let subst = unify(&Type::from_var(None, var.clone()), &Type::from_var(None, var.clone()))
+            .unwrap();
+        assert!(subst.get(var.id()).is_none());
+    }
+
+    #[test]
+    fn unify_rejects_infinite_types() {
+        let var = TypeVar::fresh(Some("T"));
+        let mut builder = Type::builder(String::new());
+        builder
+            .array()
+            .set_element_type(Type::from_var(None, var.clone()))
+            .len(1)
+            .finish();
+        let array_of_var = builder.build();
+        assert!(matches!(
+            unify(&Type::from_var(None, var), &array_of_var),
+            Err(UnifyError::InfiniteType)
+        ));
+    }
+
+    #[test]
+    fn unify_rejects_a_forall_instead_of_comparing_it_structurally() {
+        let var = TypeVar::fresh(Some("T"));
+        let mut builder = Type::builder(String::new());
+        builder.forall(vec![var.clone()], Type::from_var(None, var));
+        let scheme = builder.build();
+
+        assert!(matches!(
+            unify(&scheme.clone(), &scheme),
+            Err(UnifyError::UnquantifiedForall)
+        ));
+    }
+
+    #[test]
+    fn unify_rejects_a_forall_against_a_plain_type_variable() {
+        let bound = TypeVar::fresh(Some("T"));
+        let mut builder = Type::builder(String::new());
+        builder.forall(vec![bound.clone()], Type::from_var(None, bound));
+        let scheme = builder.build();
+
+        let inference_var = TypeVar::fresh(Some("U"));
+        assert!(matches!(
+            unify(&Type::from_var(None, inference_var), &scheme),
+            Err(UnifyError::UnquantifiedForall)
+        ));
+    }
+
+    #[test]
+    fn generalize_quantifies_over_free_variables() {
+        let var = TypeVar::fresh(Some("T"));
+        let scheme = generalize(Type::from_var(None, var.clone()), &[]);
+        match scheme.kind() {
+            TypeKind::Forall(forall) => assert_eq!(forall.vars(), &[var]),
+            other => panic!("expected a Forall, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn generalize_excludes_variables_bound_by_the_environment() {
+        let var = TypeVar::fresh(Some("T"));
+        let scheme = generalize(Type::from_var(None, var.clone()), std::slice::from_ref(&var));
+        assert_eq!(scheme, Type::from_var(None, var));
+    }
+
+    #[test]
+    fn instantiate_replaces_bound_vars_with_fresh_ones() {
+        let var = TypeVar::fresh(Some("T"));
+        let mut builder = Type::builder(String::new());
+        builder.forall(vec![var.clone()], Type::from_var(None, var.clone()));
+        let forall = match builder.build().kind() {
+            TypeKind::Forall(forall) => forall.clone(),
+            _ => unreachable!(),
+        };
+
+        let instantiated = instantiate(&forall);
+        match instantiated.kind() {
+            TypeKind::Var(fresh_var) => assert_ne!(fresh_var.id(), var.id()),
+            other => panic!("expected a Var, got {other:?}"),
+        }
+    }
+}
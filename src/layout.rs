@@ -0,0 +1,247 @@
+use crate::types::TypeKind;
+use crate::{Enum, Field, Struct, Type};
+
+/// Pointer size assumed for function types, in bytes.
+const POINTER_SIZE: usize = 8;
+
+/// The size and alignment of a [`Type`]'s in-memory representation, in
+/// bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Layout {
+    size: usize,
+    align: usize,
+}
+
+impl Layout {
+    fn new(size: usize, align: usize) -> Self {
+        Self { size, align }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn align(&self) -> usize {
+        self.align
+    }
+}
+
+impl Type {
+    /// Computes the size and alignment of this type's in-memory
+    /// representation.
+    ///
+    /// # Panics
+    /// Panics if called on a non-concrete (generic) type, i.e. a type
+    /// variable or a universally-quantified scheme; instantiate it first.
+    #[must_use]
+    pub fn layout(&self) -> Layout {
+        self.try_layout().unwrap_or_else(|| {
+            panic!("cannot compute the layout of a non-concrete (generic) type; instantiate it first")
+        })
+    }
+
+    /// Computes the size and alignment of this type's in-memory
+    /// representation, or `None` if `self` is a non-concrete (generic)
+    /// type, i.e. a type variable or a universally-quantified scheme.
+    #[must_use]
+    pub fn try_layout(&self) -> Option<Layout> {
+        match self.kind() {
+            TypeKind::Int(int_type) | TypeKind::UInt(int_type) => {
+                let bytes = bytes_for_bits(int_type.bits());
+                Some(Layout::new(bytes, bytes))
+            }
+            TypeKind::Array(array) => {
+                let element = array.element_type().try_layout()?;
+                Some(Layout::new(element.size() * array.len(), element.align()))
+            }
+            TypeKind::Struct(structure) => Some(fields_layout(structure.fields().iter())?.1),
+            TypeKind::Enum(enumeration) => enum_layout(enumeration),
+            TypeKind::Function(_) => Some(Layout::new(POINTER_SIZE, POINTER_SIZE)),
+            TypeKind::Var(_) | TypeKind::Forall(_) => None,
+        }
+    }
+}
+
+impl Struct {
+    /// Computes the byte offset of each field, in declaration order,
+    /// accounting for padding introduced by each field's alignment.
+    ///
+    /// # Panics
+    /// Panics if any field is a non-concrete (generic) type; instantiate
+    /// it first.
+    #[must_use]
+    pub fn field_offsets(&self) -> Vec<usize> {
+        fields_layout(self.fields().iter())
+            .expect(
+                "cannot compute the layout of a non-concrete (generic) type; instantiate it first",
+            )
+            .0
+    }
+}
+
+/// Lays out a sequence of fields one after another, padding each to its own
+/// alignment, and returns their offsets alongside the layout of the whole.
+///
+/// Returns `None` if any field is a non-concrete (generic) type.
+fn fields_layout<'a>(fields: impl Iterator<Item = &'a Field>) -> Option<(Vec<usize>, Layout)> {
+    let mut offset = 0;
+    let mut align = 1;
+    let mut offsets = Vec::new();
+
+    for field in fields {
+        let field_layout = field.field_type().try_layout()?;
+        offset = round_up(offset, field_layout.align());
+        offsets.push(offset);
+        offset += field_layout.size();
+        align = align.max(field_layout.align());
+    }
+
+    Some((offsets, Layout::new(round_up(offset, align), align)))
+}
+
+/// Tagged-union layout: a discriminant wide enough to distinguish every
+/// variant, followed by the largest variant payload.
+///
+/// Returns `None` if any variant field is a non-concrete (generic) type.
+fn enum_layout(enumeration: &Enum) -> Option<Layout> {
+    let variant_count = enumeration.variants().len() as u32;
+    let tag_bits = if variant_count <= 1 {
+        1
+    } else {
+        32 - (variant_count - 1).leading_zeros()
+    };
+    let tag = bytes_for_bits(tag_bits as u16);
+
+    let payload = enumeration.variants().iter().try_fold(
+        Layout::new(0, 1),
+        |acc, variant| -> Option<Layout> {
+            let variant_layout = fields_layout(variant.fields().iter())?.1;
+            Some(Layout::new(
+                acc.size().max(variant_layout.size()),
+                acc.align().max(variant_layout.align()),
+            ))
+        },
+    )?;
+
+    let align = tag.max(payload.align());
+    let payload_offset = round_up(tag, payload.align());
+    Some(Layout::new(
+        round_up(payload_offset + payload.size(), align),
+        align,
+    ))
+}
+
+/// Rounds `bits` up to the next power-of-two byte count.
+fn bytes_for_bits(bits: u16) -> usize {
+    let bytes = usize::from(bits).div_ceil(8);
+    bytes.next_power_of_two().max(1)
+}
+
+/// Rounds `value` up to the nearest multiple of `align`.
+fn round_up(value: usize, align: usize) -> usize {
+    if align == 0 {
+        return value;
+    }
+    value.div_ceil(align) * align
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Field;
+
+    fn int(bits: u16) -> Type {
+        let mut builder = Type::builder(String::new());
+        builder.int().set_bits(bits).finish();
+        builder.build()
+    }
+
+    #[test]
+    fn int_layout_rounds_up_to_a_power_of_two_byte_count() {
+        let layout = int(12).layout();
+        assert_eq!(layout.size(), 2);
+        assert_eq!(layout.align(), 2);
+    }
+
+    #[test]
+    fn struct_layout_pads_fields_to_their_alignment() {
+        let mut builder = Type::builder(String::new());
+        builder
+            .structure()
+            .add_field(Field::new(Some("a"), int(8)))
+            .unwrap()
+            .add_field(Field::new(Some("b"), int(32)))
+            .unwrap()
+            .finish();
+        let structure = builder.build();
+
+        assert_eq!(structure.layout().size(), 8);
+        assert_eq!(structure.layout().align(), 4);
+    }
+
+    #[test]
+    fn field_offsets_account_for_padding() {
+        let mut builder = Type::builder(String::new());
+        builder
+            .structure()
+            .add_field(Field::new(Some("a"), int(8)))
+            .unwrap()
+            .add_field(Field::new(Some("b"), int(32)))
+            .unwrap()
+            .finish();
+        let structure = builder.build();
+
+        let offsets = match structure.kind() {
+            TypeKind::Struct(structure) => structure.field_offsets(),
+            _ => unreachable!(),
+        };
+        assert_eq!(offsets, vec![0, 4]);
+    }
+
+    #[test]
+    fn array_layout_multiplies_element_size_by_length() {
+        let mut builder = Type::builder(String::new());
+        builder
+            .array()
+            .set_element_type(int(32))
+            .len(3)
+            .finish();
+        let array = builder.build();
+
+        assert_eq!(array.layout().size(), 12);
+        assert_eq!(array.layout().align(), 4);
+    }
+
+    #[test]
+    fn try_layout_returns_none_for_a_type_variable() {
+        let mut builder = Type::builder(String::new());
+        builder.type_var("T");
+        let var = builder.build();
+        assert!(var.try_layout().is_none());
+    }
+
+    #[test]
+    fn try_layout_returns_none_for_a_nested_type_variable() {
+        let mut var_builder = Type::builder(String::new());
+        var_builder.type_var("T");
+        let var = var_builder.build();
+
+        let mut struct_builder = Type::builder(String::new());
+        struct_builder
+            .structure()
+            .add_field(Field::new(Some("generic_field"), var))
+            .unwrap()
+            .finish();
+        let structure = struct_builder.build();
+
+        assert!(structure.try_layout().is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "non-concrete")]
+    fn layout_panics_for_a_type_variable() {
+        let mut builder = Type::builder(String::new());
+        builder.type_var("T");
+        let _ = builder.build().layout();
+    }
+}
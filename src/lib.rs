@@ -0,0 +1,13 @@
+pub mod codec;
+pub mod dynamic;
+pub mod infer;
+pub mod layout;
+pub mod types;
+
+pub use codec::DecodeError;
+pub use dynamic::Dynamic;
+pub use layout::Layout;
+pub use types::{
+    Array, Enum, Field, Folder, Forall, Function, IntType, Parameter, Struct, Type, TypeBuilder,
+    TypeVar, Variant, VariantBuilder, Visitor,
+};
@@ -2,11 +2,24 @@ mod visitors;
 pub use visitors::Visitor;
 
 mod builders;
-pub use builders::TypeBuilder;
+pub use builders::{TypeBuilder, VariantBuilder};
+
+mod fold;
+pub use fold::Folder;
 
 #[derive(Debug, Clone)]
 pub struct Type(Box<TypeInfo>);
 
+impl PartialEq for Type {
+    /// Structural equality: two types are equal if their `TypeKind`s are
+    /// equal, regardless of the (possibly absent) name attached to either.
+    fn eq(&self, other: &Self) -> bool {
+        self.0.kind == other.0.kind
+    }
+}
+
+impl Eq for Type {}
+
 impl Type {
     #[must_use]
     pub fn builder(name: String) -> TypeBuilder {
@@ -25,6 +38,43 @@ impl Type {
             TypeKind::UInt(int_type) => visitor.visit_uint(int_type),
             TypeKind::Array(array) => visitor.visit_array(array),
             TypeKind::Function(function) => visitor.visit_function(function),
+            TypeKind::Var(var) => visitor.visit_var(var),
+            TypeKind::Forall(forall) => visitor.visit_forall(forall),
+        }
+    }
+
+    /// Gives other crate-internal modules (e.g. `infer`) access to the
+    /// underlying `TypeKind` without exposing it as part of the public API.
+    pub(crate) fn kind(&self) -> &TypeKind {
+        &self.0.kind
+    }
+
+    /// Constructs a type directly from a `TypeVar`, without going through
+    /// `TypeBuilder`. Used internally when rebuilding types during folding,
+    /// substitution and decoding, where the variable already exists.
+    pub(crate) fn from_var(name: Option<String>, var: TypeVar) -> Type {
+        Type(Box::new(TypeInfo {
+            name,
+            kind: TypeKind::Var(var),
+        }))
+    }
+
+    /// Consumes this type and rebuilds it through `folder`, dispatching on
+    /// its `TypeKind`. Unlike `visit`, this can rewrite the tree: each
+    /// matched node is handed to the corresponding `Folder` method, whose
+    /// default implementation recurses into children before reconstructing
+    /// them via the usual builders.
+    pub fn fold<F: Folder>(self, folder: &mut F) -> Type {
+        let TypeInfo { name, kind } = *self.0;
+        match kind {
+            TypeKind::Enum(enumeration) => folder.fold_enum(name, enumeration),
+            TypeKind::Struct(structure) => folder.fold_struct(name, structure),
+            TypeKind::Int(int_type) => folder.fold_int(name, int_type),
+            TypeKind::UInt(int_type) => folder.fold_uint(name, int_type),
+            TypeKind::Array(array) => folder.fold_array(name, array),
+            TypeKind::Function(function) => folder.fold_function(name, function),
+            TypeKind::Var(var) => folder.fold_var(name, var),
+            TypeKind::Forall(forall) => folder.fold_forall(name, forall),
         }
     }
 }
@@ -35,17 +85,19 @@ pub struct TypeInfo {
     kind: TypeKind,
 }
 
-#[derive(Debug, Clone)]
-enum TypeKind {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum TypeKind {
     Int(IntType),
     UInt(IntType),
     Enum(Enum),
     Struct(Struct),
     Array(Array),
     Function(Function),
+    Var(TypeVar),
+    Forall(Forall),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct IntType {
     bits: u16,
 }
@@ -56,7 +108,7 @@ impl IntType {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Struct {
     fields: Vec<Field>,
 }
@@ -67,7 +119,7 @@ impl Struct {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Field {
     name: Option<String>,
     ty: Type,
@@ -90,7 +142,7 @@ impl Field {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Enum {
     variants: Vec<Variant>,
 }
@@ -101,22 +153,41 @@ impl Enum {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Variant {
     name: String,
+    fields: Vec<Field>,
+    discriminant: Option<i64>,
 }
 
 impl Variant {
     pub fn new(name: impl Into<String>) -> Self {
-        Self { name: name.into() }
+        Self {
+            name: name.into(),
+            fields: Vec::new(),
+            discriminant: None,
+        }
+    }
+
+    #[must_use]
+    pub fn builder(name: impl Into<String>) -> VariantBuilder {
+        VariantBuilder::new(name)
     }
 
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    pub fn discriminant(&self) -> Option<i64> {
+        self.discriminant
+    }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Array {
     element_type: Type,
     len: usize,
@@ -154,6 +225,22 @@ impl Function {
     }
 }
 
+impl PartialEq for Function {
+    /// Structural equality: only parameter types (not names) and the
+    /// return type participate; the body is not part of the type.
+    fn eq(&self, other: &Self) -> bool {
+        self.parameters.len() == other.parameters.len()
+            && self
+                .parameters
+                .iter()
+                .zip(&other.parameters)
+                .all(|(a, b)| a.ty == b.ty)
+            && self.return_type == other.return_type
+    }
+}
+
+impl Eq for Function {}
+
 #[derive(Debug, Clone)]
 pub struct Parameter {
     name: String,
@@ -176,3 +263,142 @@ impl Parameter {
         &self.ty
     }
 }
+
+/// An interned, universally-quantifiable type variable.
+///
+/// Equality and hashing are based solely on the interned `id`; the display
+/// `name` is carried along only for diagnostics and is never compared.
+#[derive(Debug, Clone)]
+pub struct TypeVar {
+    id: u32,
+    name: Option<String>,
+}
+
+/// Process-wide counter backing [`TypeVar::fresh`]. Also bumped by
+/// [`TypeVar::from_raw`] so that a deserialized id is never re-minted by a
+/// later `fresh()` call, which would make two unrelated variables compare
+/// equal.
+///
+/// Widened to 64 bits so that reserving the id space right up to
+/// `u32::MAX` (as a decoded `TypeVar` may do) can't wrap back around to an
+/// id that's already in use; `fresh()` instead fails loudly once the
+/// 32-bit id space is actually exhausted.
+static NEXT_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+impl TypeVar {
+    /// Mints a fresh type variable with a process-wide unique id.
+    ///
+    /// # Panics
+    /// Panics if the 32-bit id space has been exhausted.
+    #[must_use]
+    pub fn fresh(name: Option<impl Into<String>>) -> Self {
+        let id = NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        Self {
+            id: u32::try_from(id).expect("exhausted the 32-bit TypeVar id space"),
+            name: name.map(Into::into),
+        }
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Reconstructs a `TypeVar` with a specific id, bypassing the
+    /// freshness counter. Used internally by the `codec` module, which
+    /// must restore the exact id a variable had when it was encoded.
+    ///
+    /// Reserves `id` against the `fresh()` counter so a subsequent
+    /// `TypeVar::fresh` call can never mint the same id again.
+    pub(crate) fn from_raw(id: u32, name: Option<String>) -> Self {
+        NEXT_ID.fetch_max(u64::from(id) + 1, std::sync::atomic::Ordering::Relaxed);
+        Self { id, name }
+    }
+}
+
+impl PartialEq for TypeVar {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for TypeVar {}
+
+impl std::hash::Hash for TypeVar {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+/// A type scheme: a `body` type universally quantified over `vars`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Forall {
+    vars: Vec<TypeVar>,
+    body: Type,
+}
+
+impl Forall {
+    pub fn vars(&self) -> &[TypeVar] {
+        &self.vars
+    }
+
+    pub fn body(&self) -> &Type {
+        &self.body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int(bits: u16) -> Type {
+        let mut builder = Type::builder(String::new());
+        builder.int().set_bits(bits).finish();
+        builder.build()
+    }
+
+    #[test]
+    fn equality_ignores_name() {
+        let mut a_builder = Type::builder("A".into());
+        a_builder.int().set_bits(32).finish();
+        let mut b_builder = Type::builder("B".into());
+        b_builder.int().set_bits(32).finish();
+
+        assert_eq!(a_builder.build(), b_builder.build());
+    }
+
+    #[test]
+    fn equality_distinguishes_kind() {
+        assert_ne!(int(32), int(64));
+    }
+
+    #[test]
+    fn from_var_preserves_outer_name() {
+        let var = TypeVar::fresh(Some("T"));
+        let ty = Type::from_var(Some("MyNamedVar".to_string()), var);
+        assert_eq!(ty.name(), Some("MyNamedVar"));
+    }
+
+    #[test]
+    fn fresh_type_vars_get_distinct_ids() {
+        let a = TypeVar::fresh(Some("a"));
+        let b = TypeVar::fresh(Some("b"));
+        assert_ne!(a.id(), b.id());
+    }
+
+    #[test]
+    fn from_raw_reserves_the_id_against_future_fresh_calls() {
+        // Jump well ahead of wherever the process-wide counter happens to
+        // be (shared with every other test in this binary) without
+        // pushing it anywhere near `u32::MAX`, which other tests rely on
+        // still being mintable.
+        let ahead = TypeVar::fresh(None::<String>).id() + 1_000_000;
+        let raw = TypeVar::from_raw(ahead, None);
+        let fresh = TypeVar::fresh(None::<String>);
+        assert_ne!(raw.id(), fresh.id());
+        assert!(fresh.id() > raw.id());
+    }
+}
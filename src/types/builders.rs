@@ -1,7 +1,54 @@
 use super::{
-    Array, Field, Function, IntType, Parameter, Struct, Type, TypeInfo, TypeKind, Variant,
+    Array, Field, Forall, Function, IntType, Parameter, Struct, Type, TypeInfo, TypeKind, TypeVar,
+    Variant,
 };
 
+/// Builder for creating enum [`Variant`]s, optionally with a field payload
+/// and an explicit discriminant.
+pub struct VariantBuilder {
+    name: String,
+    fields: Vec<Field>,
+    discriminant: Option<i64>,
+}
+
+impl VariantBuilder {
+    /// Creates a new `VariantBuilder` with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            fields: Vec::new(),
+            discriminant: None,
+        }
+    }
+
+    /// Adds a payload field to the variant.
+    ///
+    /// Returns an error if a duplicate field is added.
+    pub fn add_field(mut self, field: Field) -> Result<Self, BuilderError> {
+        if self.fields.iter().any(|f| f.name == field.name) {
+            Err(BuilderError::DuplicateValue("field"))
+        } else {
+            self.fields.push(field);
+            Ok(self)
+        }
+    }
+
+    /// Sets an explicit discriminant value for the variant.
+    pub fn set_discriminant(mut self, discriminant: i64) -> Self {
+        self.discriminant = Some(discriminant);
+        self
+    }
+
+    /// Finishes building the variant.
+    pub fn build(self) -> Variant {
+        Variant {
+            name: self.name,
+            fields: self.fields,
+            discriminant: self.discriminant,
+        }
+    }
+}
+
 /// Error type for builder operations.
 #[derive(Debug, thiserror::Error)]
 pub enum BuilderError {
@@ -64,6 +111,19 @@ impl TypeBuilder {
         FunctionBuilder::new(self)
     }
 
+    /// Builds a fresh, universally-quantifiable type variable with the
+    /// given display name.
+    pub fn type_var<N: Into<String>>(&mut self, name: N) -> &mut Self {
+        self.kind = Some(TypeKind::Var(TypeVar::fresh(Some(name.into()))));
+        self
+    }
+
+    /// Wraps `body` in a type scheme universally quantified over `vars`.
+    pub fn forall(&mut self, vars: Vec<TypeVar>, body: Type) -> &mut Self {
+        self.kind = Some(TypeKind::Forall(Forall { vars, body }));
+        self
+    }
+
     /// Tries to build the `Type`, returning a `Result`.
     ///
     /// Returns an error if no type kind has been set.
@@ -102,10 +162,11 @@ impl<'a> EnumBuilder<'a> {
         }
     }
 
-    /// Adds a variant to the enumeration.
+    /// Adds a variant to the enumeration, built via a [`VariantBuilder`].
     ///
     /// Returns an error if a duplicate variant is added.
-    pub fn add_variant(mut self, variant: Variant) -> Result<Self, BuilderError> {
+    pub fn add_variant(mut self, variant: VariantBuilder) -> Result<Self, BuilderError> {
+        let variant = variant.build();
         if self.variants.iter().any(|v| v.name == variant.name) {
             Err(BuilderError::DuplicateValue("variant"))
         } else {
@@ -324,3 +385,105 @@ impl<'a> FunctionBuilder<'a> {
         self.try_finish().unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Enum, Visitor};
+
+    fn int(bits: u16) -> Type {
+        let mut builder = Type::builder(String::new());
+        builder.int().set_bits(bits).finish();
+        builder.build()
+    }
+
+    #[test]
+    fn variant_builder_rejects_a_duplicate_field_name() {
+        let builder = Variant::builder("Some").add_field(Field::new(Some("value"), int(32))).unwrap();
+
+        assert!(matches!(
+            builder.add_field(Field::new(Some("value"), int(64))),
+            Err(BuilderError::DuplicateValue("field"))
+        ));
+    }
+
+    #[test]
+    fn enum_builder_rejects_a_duplicate_variant_name() {
+        let mut builder = Type::builder(String::new());
+        let enum_builder = builder
+            .enumeration()
+            .add_variant(Variant::builder("Some").add_field(Field::new(Some("value"), int(32))).unwrap())
+            .unwrap();
+
+        assert!(matches!(
+            enum_builder.add_variant(Variant::builder("Some").set_discriminant(1)),
+            Err(BuilderError::DuplicateValue("variant"))
+        ));
+    }
+
+    #[test]
+    fn variant_discriminant_and_fields_round_trip_through_the_builder() {
+        let variant = Variant::builder("Some")
+            .add_field(Field::new(Some("value"), int(32)))
+            .unwrap()
+            .set_discriminant(7)
+            .build();
+
+        assert_eq!(variant.discriminant(), Some(7));
+        assert_eq!(variant.fields().len(), 1);
+        assert_eq!(variant.fields()[0].name(), Some("value"));
+    }
+
+    /// A `Visitor` that records the names of every variant and field it
+    /// walks into, to check that recursing through an enum's variants
+    /// (and a variant's fields) reaches the payload built via
+    /// [`VariantBuilder`].
+    struct RecordingVisitor {
+        seen: std::cell::RefCell<Vec<String>>,
+    }
+
+    impl Visitor for RecordingVisitor {
+        fn visit_enum(&self, enumeration: &Enum) {
+            for variant in enumeration.variants() {
+                self.visit_variant(variant);
+            }
+        }
+
+        fn visit_variant(&self, variant: &Variant) {
+            self.seen.borrow_mut().push(variant.name().to_string());
+            for field in variant.fields() {
+                if let Some(name) = field.name() {
+                    self.seen.borrow_mut().push(name.to_string());
+                }
+            }
+        }
+
+        fn visit_struct(&self, _structure: &Struct) {}
+        fn visit_int(&self, _int_type: &IntType) {}
+        fn visit_uint(&self, _int_type: &IntType) {}
+        fn visit_array(&self, _array: &Array) {}
+        fn visit_function(&self, _function: &Function) {}
+        fn visit_var(&self, _var: &TypeVar) {}
+        fn visit_forall(&self, _forall: &Forall) {}
+    }
+
+    #[test]
+    fn visitor_walks_into_variant_fields() {
+        let mut builder = Type::builder(String::new());
+        builder
+            .enumeration()
+            .add_variant(Variant::builder("Some").add_field(Field::new(Some("value"), int(32))).unwrap())
+            .unwrap()
+            .add_variant(Variant::builder("None"))
+            .unwrap()
+            .finish();
+        let enum_type = builder.build();
+
+        let visitor = RecordingVisitor {
+            seen: std::cell::RefCell::new(Vec::new()),
+        };
+        enum_type.visit(&visitor);
+
+        assert_eq!(visitor.seen.into_inner(), vec!["Some", "value", "None"]);
+    }
+}
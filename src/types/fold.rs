@@ -0,0 +1,173 @@
+use super::{
+    Array, Enum, Field, Forall, Function, IntType, Parameter, Struct, Type, TypeVar, Variant,
+};
+
+/// A transforming counterpart to [`Visitor`](super::Visitor): each method
+/// consumes a type node and returns a possibly-rewritten [`Type`].
+///
+/// Default implementations recurse into children and reconstruct an
+/// equivalent type through the existing builders, so an implementor only
+/// needs to override the node kinds it actually wants to change.
+pub trait Folder: Sized {
+    fn fold_int(&mut self, name: Option<String>, int_type: IntType) -> Type {
+        let mut builder = Type::builder(name.unwrap_or_default());
+        builder.int().set_bits(int_type.bits()).finish();
+        builder.build()
+    }
+
+    fn fold_uint(&mut self, name: Option<String>, int_type: IntType) -> Type {
+        let mut builder = Type::builder(name.unwrap_or_default());
+        builder.uint().set_bits(int_type.bits()).finish();
+        builder.build()
+    }
+
+    fn fold_array(&mut self, name: Option<String>, array: Array) -> Type {
+        let element = array.element_type().clone().fold(self);
+        let mut builder = Type::builder(name.unwrap_or_default());
+        builder.array().set_element_type(element).len(array.len()).finish();
+        builder.build()
+    }
+
+    fn fold_struct(&mut self, name: Option<String>, structure: Struct) -> Type {
+        let mut builder = Type::builder(name.unwrap_or_default());
+        {
+            let mut struct_builder = builder.structure();
+            for field in structure.fields() {
+                let new_type = field.field_type().clone().fold(self);
+                struct_builder = struct_builder
+                    .add_field(Field::new(field.name(), new_type))
+                    .expect("folding a struct cannot introduce duplicate fields");
+            }
+            struct_builder.finish();
+        }
+        builder.build()
+    }
+
+    fn fold_enum(&mut self, name: Option<String>, enumeration: Enum) -> Type {
+        let mut builder = Type::builder(name.unwrap_or_default());
+        {
+            let mut enum_builder = builder.enumeration();
+            for variant in enumeration.variants() {
+                let folded = self.fold_variant(variant.clone());
+                let mut variant_builder = Variant::builder(folded.name());
+                if let Some(discriminant) = folded.discriminant() {
+                    variant_builder = variant_builder.set_discriminant(discriminant);
+                }
+                for field in folded.fields() {
+                    variant_builder = variant_builder
+                        .add_field(field.clone())
+                        .expect("folding an enum cannot introduce duplicate fields");
+                }
+                enum_builder = enum_builder
+                    .add_variant(variant_builder)
+                    .expect("folding an enum cannot introduce duplicate variants");
+            }
+            enum_builder.finish();
+        }
+        builder.build()
+    }
+
+    fn fold_variant(&mut self, variant: Variant) -> Variant {
+        let mut builder = Variant::builder(variant.name().to_string());
+        if let Some(discriminant) = variant.discriminant() {
+            builder = builder.set_discriminant(discriminant);
+        }
+        for field in variant.fields() {
+            let new_type = field.field_type().clone().fold(self);
+            builder = builder
+                .add_field(Field::new(field.name(), new_type))
+                .expect("folding a variant cannot introduce duplicate fields");
+        }
+        builder.build()
+    }
+
+    fn fold_function(&mut self, name: Option<String>, function: Function) -> Type {
+        let mut builder = Type::builder(name.unwrap_or_default());
+        {
+            let mut function_builder = builder.function();
+            for parameter in function.parameters() {
+                let new_type = parameter.parameter_type().clone().fold(self);
+                function_builder =
+                    function_builder.add_parameter(Parameter::new(parameter.name(), new_type));
+            }
+            let return_type = function.return_type().clone().fold(self);
+            function_builder = function_builder.set_return_type(return_type);
+            if let Some(body) = function.body() {
+                function_builder = function_builder.set_body(body);
+            }
+            function_builder.finish();
+        }
+        builder.build()
+    }
+
+    fn fold_var(&mut self, name: Option<String>, var: TypeVar) -> Type {
+        Type::from_var(name, var)
+    }
+
+    fn fold_forall(&mut self, name: Option<String>, forall: Forall) -> Type {
+        let Forall { vars, body } = forall;
+        let new_body = body.fold(self);
+        let mut builder = Type::builder(name.unwrap_or_default());
+        builder.forall(vars, new_body);
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TypeKind;
+
+    /// A `Folder` that performs no rewriting, used to check that the
+    /// default method bodies reconstruct an equivalent type.
+    struct Identity;
+    impl Folder for Identity {}
+
+    #[test]
+    fn identity_fold_preserves_an_int_type() {
+        let mut builder = Type::builder("MyInt".to_string());
+        builder.int().set_bits(32).finish();
+        let ty = builder.build();
+
+        let folded = ty.clone().fold(&mut Identity);
+        assert_eq!(folded, ty);
+        assert_eq!(folded.name(), Some("MyInt"));
+    }
+
+    #[test]
+    fn default_fold_var_preserves_the_outer_name() {
+        let var = TypeVar::fresh(Some("T"));
+        let ty = Type::from_var(Some("MyNamedVar".to_string()), var);
+
+        let folded = ty.fold(&mut Identity);
+        assert_eq!(folded.name(), Some("MyNamedVar"));
+    }
+
+    struct RenameInts;
+    impl Folder for RenameInts {
+        fn fold_int(&mut self, _name: Option<String>, int_type: IntType) -> Type {
+            let mut builder = Type::builder("renamed".to_string());
+            builder.int().set_bits(int_type.bits()).finish();
+            builder.build()
+        }
+    }
+
+    #[test]
+    fn fold_recurses_into_array_elements() {
+        let mut builder = Type::builder(String::new());
+        let mut element_builder = Type::builder("original".to_string());
+        element_builder.int().set_bits(8).finish();
+        builder
+            .array()
+            .set_element_type(element_builder.build())
+            .len(4)
+            .finish();
+        let ty = builder.build();
+
+        let folded = ty.fold(&mut RenameInts);
+        match folded.kind() {
+            TypeKind::Array(array) => assert_eq!(array.element_type().name(), Some("renamed")),
+            other => panic!("expected an Array, got {other:?}"),
+        }
+    }
+}
@@ -1,8 +1,10 @@
-use super::{Array, Enum, Function, IntType, Struct};
+use super::{Array, Enum, Forall, Function, IntType, Struct, TypeVar, Variant};
 
 pub trait Visitor {
     fn visit_enum(&self, enumeration: &Enum);
 
+    fn visit_variant(&self, variant: &Variant);
+
     fn visit_struct(&self, structure: &Struct);
 
     fn visit_int(&self, int_type: &IntType);
@@ -12,4 +14,8 @@ pub trait Visitor {
     fn visit_array(&self, array: &Array);
 
     fn visit_function(&self, function: &Function);
+
+    fn visit_var(&self, var: &TypeVar);
+
+    fn visit_forall(&self, forall: &Forall);
 }